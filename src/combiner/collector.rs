@@ -0,0 +1,150 @@
+use super::ignore::{IgnoreMatcher, ScopedIgnore};
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Flat file list and the same tree rendered as indented text, produced
+// together so a directory is only traversed once per output needed.
+pub struct CollectedFiles {
+    pub files: Vec<PathBuf>,
+    pub tree: String,
+}
+
+// Walks one base directory, pruning ignored (and, by default, hidden)
+// entries before recursing into them rather than collecting everything
+// and filtering afterwards.
+//
+// Note: this does not split include/extension patterns into per-subtree
+// base directories. There's no glob-style include syntax in this CLI to
+// split in the first place (callers pass literal base paths plus a flat
+// extension list), so there's nothing to carve up beyond the per-path
+// traversal `collect_paths` already does in `mod.rs`.
+pub struct FileCollector<'a> {
+    file_extensions: Option<&'a [String]>,
+    ignore_matcher: &'a IgnoreMatcher,
+    include_hidden: bool,
+    no_ignore: bool,
+}
+
+impl<'a> FileCollector<'a> {
+    pub fn new(
+        file_extensions: Option<&'a [String]>,
+        ignore_matcher: &'a IgnoreMatcher,
+        include_hidden: bool,
+        no_ignore: bool,
+    ) -> Self {
+        FileCollector {
+            file_extensions,
+            ignore_matcher,
+            include_hidden,
+            no_ignore,
+        }
+    }
+
+    // Starts traversal at `base` directly instead of `root`, so a caller
+    // that only cares about one subtree never descends into the rest, but
+    // still anchors ignore patterns at `root` so root-anchored patterns
+    // (e.g. `/src/generated`) keep matching regardless of which subtree
+    // traversal actually starts from.
+    pub fn collect(&self, base: &Path, root: &Path) -> CollectedFiles {
+        let scope = self.scope_for(root, base);
+        let mut files = Vec::new();
+        let mut tree = String::new();
+        self.walk(base, &scope, String::new(), &mut files, &mut tree);
+        files.sort();
+        CollectedFiles { files, tree }
+    }
+
+    // Rebuilds the ignore scope as if traversal had started at `root` and
+    // descended normally down to `base`, so ancestor `.gitignore` files
+    // between `root` and `base` are still picked up even though the walk
+    // itself only starts at `base`.
+    fn scope_for(&self, root: &Path, base: &Path) -> ScopedIgnore {
+        let mut scope = ScopedIgnore::new(root, self.ignore_matcher.clone());
+        if let Ok(relative) = base.strip_prefix(root) {
+            let mut current = root.to_path_buf();
+            for component in relative.components() {
+                current.push(component);
+                scope = self.descend(&scope, &current);
+            }
+        }
+        scope
+    }
+
+    // Merges in `dir`'s own `.gitignore`, unless `--no-ignore` asked us to
+    // skip nested ignore files entirely (the root matcher passed in still
+    // applies).
+    fn descend(&self, scope: &ScopedIgnore, dir: &Path) -> ScopedIgnore {
+        if self.no_ignore {
+            scope.clone()
+        } else {
+            scope.descend(dir, ".gitignore")
+        }
+    }
+
+    fn walk(
+        &self,
+        dir: &Path,
+        scope: &ScopedIgnore,
+        prefix: String,
+        files: &mut Vec<PathBuf>,
+        tree: &mut String,
+    ) {
+        let mut contents: Vec<_> = fs::read_dir(dir)
+            .unwrap()
+            .filter_map(Result::ok)
+            .filter(|entry| self.include_hidden || !is_hidden(&entry.file_name()))
+            .filter(|entry| !scope.is_ignored(&entry.path(), entry.path().is_dir()))
+            .collect();
+        contents.sort_by_key(|entry| (!entry.path().is_dir(), entry.file_name()));
+
+        for (i, entry) in contents.iter().enumerate() {
+            let is_last = i == contents.len() - 1;
+            let file_name = entry.file_name();
+            let path = entry.path();
+
+            if path.is_dir() {
+                tree.push_str(&format!(
+                    "{}{} {}/\n",
+                    prefix,
+                    branch(is_last),
+                    file_name.to_string_lossy()
+                ));
+                let nested_scope = self.descend(scope, &path);
+                let new_prefix = format!("{}{}   ", prefix, if is_last { " " } else { "│" });
+                self.walk(&path, &nested_scope, new_prefix, files, tree);
+            } else if self.matches_extensions(&path) {
+                tree.push_str(&format!(
+                    "{}{} {}\n",
+                    prefix,
+                    branch(is_last),
+                    file_name.to_string_lossy()
+                ));
+                files.push(path);
+            }
+        }
+    }
+
+    fn matches_extensions(&self, path: &Path) -> bool {
+        match self.file_extensions {
+            None => true,
+            Some(extensions) => extensions.iter().any(|ext| {
+                path.extension()
+                    .and_then(OsStr::to_str)
+                    .is_some_and(|e| e == ext.trim_start_matches('.'))
+            }),
+        }
+    }
+}
+
+pub(crate) fn branch(is_last: bool) -> &'static str {
+    if is_last {
+        "└──"
+    } else {
+        "├──"
+    }
+}
+
+pub fn is_hidden(file_name: &OsStr) -> bool {
+    file_name.to_string_lossy().starts_with('.')
+}
@@ -0,0 +1,196 @@
+use globset::{Glob, GlobMatcher};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone)]
+struct CompiledPattern {
+    matcher: GlobMatcher,
+    dir_only: bool,
+    negate: bool,
+}
+
+impl CompiledPattern {
+    // Same rules as git: leading `!` negates, trailing `/` is dir-only, and
+    // any other `/` anchors the pattern to the root instead of any depth.
+    fn compile(raw: &str) -> Option<Self> {
+        let mut pattern = raw;
+
+        let negate = pattern.starts_with('!');
+        if negate {
+            pattern = &pattern[1..];
+        }
+
+        let dir_only = pattern.len() > 1 && pattern.ends_with('/');
+        if dir_only {
+            pattern = &pattern[..pattern.len() - 1];
+        }
+
+        let anchored = pattern.starts_with('/') || pattern.trim_start_matches('/').contains('/');
+        let pattern = pattern.trim_start_matches('/');
+        let glob_source = if anchored {
+            pattern.to_string()
+        } else {
+            format!("**/{pattern}")
+        };
+
+        let glob = Glob::new(&glob_source).ok()?;
+        Some(CompiledPattern {
+            matcher: glob.compile_matcher(),
+            dir_only,
+            negate,
+        })
+    }
+
+    fn is_match(&self, relative_path: &Path, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        self.matcher.is_match(relative_path)
+    }
+}
+
+// Patterns are evaluated in file order, so a later pattern (including a
+// negation) overrides an earlier one, matching `.gitignore` semantics.
+#[derive(Clone, Default)]
+pub struct IgnoreMatcher {
+    patterns: Vec<CompiledPattern>,
+}
+
+impl IgnoreMatcher {
+    pub fn compile(patterns: &[String]) -> Self {
+        IgnoreMatcher {
+            patterns: patterns.iter().filter_map(|p| CompiledPattern::compile(p)).collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    // `None` means nothing matched, distinct from `Some(false)` (explicitly
+    // re-included via `!`), so a caller merging multiple scopes can tell
+    // "not mentioned" apart from "explicitly re-included".
+    fn evaluate(&self, relative_path: &Path, is_dir: bool) -> Option<bool> {
+        let mut result = None;
+        for pattern in &self.patterns {
+            if pattern.is_match(relative_path, is_dir) {
+                result = Some(!pattern.negate);
+            }
+        }
+        result
+    }
+}
+
+// The set of ignore scopes in effect while walking a tree: the root matcher
+// plus one extra matcher per ancestor directory whose own ignore file added
+// patterns. Patterns in a nested file are relative to the directory that
+// contains it, so each scope stores the base it should strip before matching.
+#[derive(Clone)]
+pub struct ScopedIgnore {
+    root: PathBuf,
+    scopes: Vec<(PathBuf, IgnoreMatcher)>,
+}
+
+impl ScopedIgnore {
+    pub fn new(root: &Path, root_matcher: IgnoreMatcher) -> Self {
+        ScopedIgnore {
+            root: root.to_path_buf(),
+            scopes: vec![(PathBuf::new(), root_matcher)],
+        }
+    }
+
+    pub fn descend(&self, dir: &Path, file_name: &str) -> Self {
+        let mut scopes = self.scopes.clone();
+        let matcher = IgnoreMatcher::compile(&read_ignore_file(&dir.join(file_name)));
+        if !matcher.is_empty() {
+            let base = dir.strip_prefix(&self.root).unwrap_or(dir).to_path_buf();
+            scopes.push((base, matcher));
+        }
+        ScopedIgnore {
+            root: self.root.clone(),
+            scopes,
+        }
+    }
+
+    pub fn is_ignored(&self, abs_path: &Path, is_dir: bool) -> bool {
+        let relative = abs_path.strip_prefix(&self.root).unwrap_or(abs_path);
+        let mut ignored = false;
+        for (base, matcher) in &self.scopes {
+            let Ok(scoped_relative) = relative.strip_prefix(base) else {
+                continue;
+            };
+            if let Some(result) = matcher.evaluate(scoped_relative, is_dir) {
+                ignored = result;
+            }
+        }
+        ignored
+    }
+}
+
+pub fn read_ignore_file(path: &Path) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(label: &str) -> PathBuf {
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("fcom-ignore-test-{label}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn anchored_pattern_matches_only_at_root() {
+        let anchored = CompiledPattern::compile("/build").unwrap();
+        assert!(anchored.is_match(Path::new("build"), true));
+        assert!(!anchored.is_match(Path::new("nested/build"), true));
+
+        let unanchored = CompiledPattern::compile("build").unwrap();
+        assert!(unanchored.is_match(Path::new("build"), true));
+        assert!(unanchored.is_match(Path::new("nested/build"), true));
+    }
+
+    #[test]
+    fn dir_only_pattern_does_not_match_files() {
+        let pattern = CompiledPattern::compile("logs/").unwrap();
+        assert!(pattern.is_match(Path::new("logs"), true));
+        assert!(!pattern.is_match(Path::new("logs"), false));
+    }
+
+    #[test]
+    fn negation_overrides_earlier_match() {
+        let matcher = IgnoreMatcher::compile(&["*.log".to_string(), "!keep.log".to_string()]);
+        assert_eq!(matcher.evaluate(Path::new("debug.log"), false), Some(true));
+        assert_eq!(matcher.evaluate(Path::new("keep.log"), false), Some(false));
+        assert_eq!(matcher.evaluate(Path::new("other.txt"), false), None);
+    }
+
+    #[test]
+    fn nested_scope_takes_precedence_over_root_scope() {
+        let root = temp_dir("nested-scope");
+        let nested = root.join("sub");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join(".gitignore"), "!keep.rs\n").unwrap();
+
+        let root_matcher = IgnoreMatcher::compile(&["*.rs".to_string()]);
+        let scope = ScopedIgnore::new(&root, root_matcher);
+        let nested_scope = scope.descend(&nested, ".gitignore");
+
+        assert!(scope.is_ignored(&nested.join("keep.rs"), false));
+        assert!(!nested_scope.is_ignored(&nested.join("keep.rs"), false));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,8 +1,14 @@
 use chrono::Local;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
+mod collector;
+pub mod ignore;
+mod paths;
+mod symbols;
 mod template;
+use collector::FileCollector;
+use ignore::IgnoreMatcher;
 use template::CombinerTemplate;
 
 const XML_OUTPUT_TEMPLATE: &str = r#"<file_overview>
@@ -58,37 +64,178 @@ const MARKDOWN_FILE_TEMPLATE: &str = r#"### {FILE_NAME}
 
 "#;
 
+const XML_OUTPUT_TEMPLATE_WITH_SYMBOLS: &str = r#"<file_overview>
+Total files: {TOTAL_FILES}
+Date generated: {DATE_GENERATED}
+Folder Structure:
+{FOLDER_TREE}
+
+Files included:
+{FILES_INCLUDED}
+
+Symbol index:
+{SYMBOL_INDEX}
+</file_overview>
+
+{FILE_CONTENTS}"#;
+
+const XML_FILE_TEMPLATE_WITH_SYMBOLS: &str = r#"<file path="{FILE_PATH}" lines="{LINES_COUNT}" modified="{MODIFIED_TIME}">
+<symbols>
+{SYMBOLS}
+</symbols>
+{FILE_CONTENT}
+</file>
+
+"#;
+
+const MARKDOWN_OUTPUT_TEMPLATE_WITH_SYMBOLS: &str = r#"# File Overview
+
+- **Total files:** {TOTAL_FILES}
+- **Date generated:** {DATE_GENERATED}
+
+## Folder Structure
+
+```
+{FOLDER_TREE}
+```
+
+## Files Included
+
+{FILES_INCLUDED}
+
+## Symbol Index
+
+```
+{SYMBOL_INDEX}
+```
+
+
+## Files Contents
+
+---
+{FILE_CONTENTS}"#;
+
+const MARKDOWN_FILE_TEMPLATE_WITH_SYMBOLS: &str = r#"### {FILE_NAME}
+
+- **Path:** `{FILE_PATH}`
+- **Lines:** {LINES_COUNT}
+- **Modified:** {MODIFIED_TIME}
+
+**Symbols:**
+```
+{SYMBOLS}
+```
+
+```
+{FILE_CONTENT}
+```
+
+---
+
+"#;
+
 pub struct FolderProcessOptions<'a> {
-    pub folder_path: &'a Path,
+    pub paths: &'a [PathBuf],
     pub output_file: &'a str,
     pub file_extensions: Option<&'a [String]>,
-    pub ignore_patterns: &'a [String],
+    pub ignore_matcher: &'a IgnoreMatcher,
     pub add_line_numbers: bool,
     pub mode: &'a str,
     pub custom_output_template: Option<&'a PathBuf>,
     pub custom_file_template: Option<&'a PathBuf>,
+    pub include_hidden: bool,
+    pub include_symbols: bool,
+    pub no_ignore: bool,
 }
 
-pub fn process_folder(options: FolderProcessOptions) {
-    if !options.folder_path.is_dir() {
-        println!(
-            "Error: The folder '{}' does not exist.",
-            options.folder_path.display()
-        );
-        return;
+fn collect_paths(
+    paths: &[PathBuf],
+    file_extensions: Option<&[String]>,
+    ignore_matcher: &IgnoreMatcher,
+    include_hidden: bool,
+    no_ignore: bool,
+) -> (PathBuf, Vec<PathBuf>, String) {
+    let canonical: Vec<PathBuf> = paths
+        .iter()
+        .filter_map(|path| match path.canonicalize() {
+            Ok(resolved) => Some(resolved),
+            Err(e) => {
+                println!("Warning: '{}' does not exist: {}", path.display(), e);
+                None
+            }
+        })
+        .collect();
+
+    let root = paths::common_root(&canonical);
+    let collector = FileCollector::new(file_extensions, ignore_matcher, include_hidden, no_ignore);
+
+    let mut all_files = Vec::new();
+    let mut tree = String::new();
+    let loose_files: Vec<&PathBuf> = canonical.iter().filter(|path| !path.is_dir()).collect();
+
+    for path in &canonical {
+        if path.is_dir() {
+            let collected = collector.collect(path, &root);
+            if canonical.len() > 1 {
+                let label = path.strip_prefix(&root).unwrap_or(path).display().to_string();
+                tree.push_str(&format!("{}/\n", if label.is_empty() { ".".to_string() } else { label }));
+                for line in collected.tree.lines() {
+                    tree.push_str("    ");
+                    tree.push_str(line);
+                    tree.push('\n');
+                }
+            } else {
+                tree.push_str(&collected.tree);
+            }
+            all_files.extend(collected.files);
+        } else {
+            all_files.push(path.clone());
+        }
     }
 
-    let all_files = get_all_files(
-        options.folder_path,
+    for (i, path) in loose_files.iter().enumerate() {
+        let is_last = i == loose_files.len() - 1;
+        let label = path.strip_prefix(&root).unwrap_or(path).display();
+        tree.push_str(&format!("{} {}\n", collector::branch(is_last), label));
+    }
+
+    all_files.sort();
+    all_files.dedup();
+    (root, all_files, tree)
+}
+
+pub fn resolve_root(paths: &[PathBuf]) -> PathBuf {
+    let canonical: Vec<PathBuf> = paths.iter().filter_map(|path| path.canonicalize().ok()).collect();
+    paths::common_root(&canonical)
+}
+
+pub fn process_folder(options: FolderProcessOptions) {
+    let (root, all_files, folder_tree) = collect_paths(
+        options.paths,
         options.file_extensions,
-        options.ignore_patterns,
+        options.ignore_matcher,
+        options.include_hidden,
+        options.no_ignore,
     );
 
+    if all_files.is_empty() {
+        println!("Error: No files found in the given paths.");
+        return;
+    }
+
     let (output_template, file_template) = match options.mode.to_lowercase().as_str() {
+        "xml" if options.include_symbols => (
+            CombinerTemplate::from_string(XML_OUTPUT_TEMPLATE_WITH_SYMBOLS),
+            CombinerTemplate::from_string(XML_FILE_TEMPLATE_WITH_SYMBOLS),
+        ),
         "xml" => (
             CombinerTemplate::from_string(XML_OUTPUT_TEMPLATE),
             CombinerTemplate::from_string(XML_FILE_TEMPLATE),
         ),
+        "markdown" if options.include_symbols => (
+            CombinerTemplate::from_string(MARKDOWN_OUTPUT_TEMPLATE_WITH_SYMBOLS),
+            CombinerTemplate::from_string(MARKDOWN_FILE_TEMPLATE_WITH_SYMBOLS),
+        ),
         "markdown" => (
             CombinerTemplate::from_string(MARKDOWN_OUTPUT_TEMPLATE),
             CombinerTemplate::from_string(MARKDOWN_FILE_TEMPLATE),
@@ -108,25 +255,26 @@ pub fn process_folder(options: FolderProcessOptions) {
         ),
     };
 
-    let folder_tree = create_folder_tree(
-        options.folder_path,
-        options.file_extensions,
-        options.ignore_patterns,
-    );
     let files_included = all_files
         .iter()
         .map(|f| {
             format!(
                 "- {}",
-                f.strip_prefix(options.folder_path).unwrap().display(),
+                f.strip_prefix(&root).unwrap_or(f).display(),
             )
         })
         .collect::<Vec<_>>()
         .join("\n");
 
+    let symbol_index = if options.include_symbols {
+        Some(symbols::extract(&all_files))
+    } else {
+        None
+    };
+
     let mut all_file_contents = Vec::new();
     for file_path in &all_files {
-        let relative_path = file_path.strip_prefix(options.folder_path).unwrap();
+        let relative_path = file_path.strip_prefix(&root).unwrap_or(file_path);
         let metadata = fs::metadata(file_path).unwrap();
         let mod_time = metadata.modified().unwrap();
         let mod_time = chrono::DateTime::<Local>::from(mod_time)
@@ -147,21 +295,22 @@ pub fn process_folder(options: FolderProcessOptions) {
                     content
                 };
 
-                let file_content = file_template.generate_output_file_content(
-                    &[
-                        ("FILE_PATH", relative_path.display().to_string()),
-                        (
-                            "FILE_NAME",
-                            file_path.file_name().unwrap().to_str().unwrap().to_string(),
-                        ),
-                        ("LINES_COUNT", line_count.to_string()),
-                        ("MODIFIED_TIME", mod_time),
-                        ("FILE_CONTENT", formatted_content.trim().to_string()),
-                    ]
-                    .iter()
-                    .cloned()
-                    .collect(),
-                );
+                let mut fields = vec![
+                    ("FILE_PATH", relative_path.display().to_string()),
+                    (
+                        "FILE_NAME",
+                        file_path.file_name().unwrap().to_str().unwrap().to_string(),
+                    ),
+                    ("LINES_COUNT", line_count.to_string()),
+                    ("MODIFIED_TIME", mod_time),
+                    ("FILE_CONTENT", formatted_content.trim().to_string()),
+                ];
+                if let Some(index) = &symbol_index {
+                    fields.push(("SYMBOLS", index.outline_for(file_path)));
+                }
+
+                let file_content = file_template
+                    .generate_output_file_content(&fields.iter().cloned().collect());
                 all_file_contents.push(file_content);
             }
             Err(e) => {
@@ -175,21 +324,22 @@ pub fn process_folder(options: FolderProcessOptions) {
         }
     }
 
-    let output_content = output_template.generate_output_file_content(
-        &[
-            ("TOTAL_FILES", all_files.len().to_string()),
-            (
-                "DATE_GENERATED",
-                Local::now().format("%Y-%m-d %H:%M:%S").to_string(),
-            ),
-            ("FOLDER_TREE", folder_tree.trim().to_string()),
-            ("FILES_INCLUDED", files_included),
-            ("FILE_CONTENTS", all_file_contents.join("")),
-        ]
-        .iter()
-        .cloned()
-        .collect(),
-    );
+    let mut output_fields = vec![
+        ("TOTAL_FILES", all_files.len().to_string()),
+        (
+            "DATE_GENERATED",
+            Local::now().format("%Y-%m-d %H:%M:%S").to_string(),
+        ),
+        ("FOLDER_TREE", folder_tree.trim().to_string()),
+        ("FILES_INCLUDED", files_included),
+        ("FILE_CONTENTS", all_file_contents.join("")),
+    ];
+    if let Some(index) = &symbol_index {
+        output_fields.push(("SYMBOL_INDEX", index.aggregate(&root)));
+    }
+
+    let output_content =
+        output_template.generate_output_file_content(&output_fields.iter().cloned().collect());
 
     fs::write(options.output_file, output_content).expect("Unable to write output file");
     println!(
@@ -199,99 +349,27 @@ pub fn process_folder(options: FolderProcessOptions) {
 }
 
 pub fn create_folder_tree(
-    path: &Path,
+    paths: &[PathBuf],
     file_extensions: Option<&[String]>,
-    ignore_folders: &[String],
+    ignore_matcher: &IgnoreMatcher,
+    include_hidden: bool,
+    no_ignore: bool,
 ) -> String {
-    create_folder_tree_inner(path, file_extensions, ignore_folders, String::new())
-}
-
-fn create_folder_tree_inner(
-    path: &Path,
-    file_extensions: Option<&[String]>,
-    ignore_folders: &[String],
-    prefix: String,
-) -> String {
-    let mut tree = String::new();
-    let mut contents: Vec<_> = fs::read_dir(path)
-        .unwrap()
-        .filter_map(Result::ok)
-        .filter(|entry| !ignore_folders.contains(&entry.file_name().to_string_lossy().into_owned()))
-        .collect();
-    contents.sort_by_key(|a| (!a.path().is_dir(), a.file_name()));
-
-    for (i, entry) in contents.iter().enumerate() {
-        let is_last = i == contents.len() - 1;
-        let file_name = entry.file_name();
-        let file_path = entry.path();
-
-        if file_path.is_dir() {
-            tree.push_str(&format!(
-                "{}{} {}/\n",
-                prefix,
-                if is_last { "└──" } else { "├──" },
-                file_name.to_string_lossy()
-            ));
-            let new_prefix = format!("{}{}   ", prefix, if is_last { " " } else { "│" });
-            tree.push_str(&create_folder_tree_inner(
-                &file_path,
-                file_extensions,
-                ignore_folders,
-                new_prefix,
-            ));
-        } else if file_extensions.is_none()
-            || file_extensions
-                .unwrap()
-                .iter()
-                .any(|ext| file_name.to_string_lossy().ends_with(ext))
-        {
-            tree.push_str(&format!(
-                "{}{} {}\n",
-                prefix,
-                if is_last { "└──" } else { "├──" },
-                file_name.to_string_lossy()
-            ));
-        }
-    }
-
-    tree
+    collect_paths(paths, file_extensions, ignore_matcher, include_hidden, no_ignore).2
 }
 
 pub fn create_file_list(
-    folder_path: &Path,
+    paths: &[PathBuf],
     file_extensions: Option<&[String]>,
-    ignore_folders: &[String],
+    ignore_matcher: &IgnoreMatcher,
+    include_hidden: bool,
+    no_ignore: bool,
 ) -> String {
-    get_all_files(folder_path, file_extensions, ignore_folders)
+    let (root, all_files, _) =
+        collect_paths(paths, file_extensions, ignore_matcher, include_hidden, no_ignore);
+    all_files
         .iter()
-        .map(|f| format!("- {}", f.strip_prefix(folder_path).unwrap().display()))
+        .map(|f| format!("- {}", f.strip_prefix(&root).unwrap_or(f).display()))
         .collect::<Vec<_>>()
         .join("\n")
 }
-
-fn get_all_files(
-    folder_path: &Path,
-    file_extensions: Option<&[String]>,
-    ignore_folders: &[String],
-) -> Vec<PathBuf> {
-    let mut all_files = Vec::new();
-    for entry in fs::read_dir(folder_path).unwrap() {
-        let entry = entry.unwrap();
-        let path = entry.path();
-        if path.is_dir() {
-            if !ignore_folders.contains(&path.file_name().unwrap().to_string_lossy().into_owned()) {
-                all_files.extend(get_all_files(&path, file_extensions, ignore_folders));
-            }
-        } else if file_extensions.is_none()
-            || file_extensions.unwrap().iter().any(|ext| {
-                path.extension()
-                    .and_then(|e| e.to_str())
-                    .map_or(false, |e| e == ext.trim_start_matches("."))
-            })
-        {
-            all_files.push(path);
-        }
-    }
-    all_files.sort();
-    all_files
-}
@@ -0,0 +1,29 @@
+use std::path::{Path, PathBuf};
+
+// Deepest directory that is an ancestor of every path in `paths`. Expects
+// `paths` to already be canonicalized.
+pub fn common_root(paths: &[PathBuf]) -> PathBuf {
+    let mut root = match paths.first() {
+        Some(first) => parent_dir(first),
+        None => return PathBuf::new(),
+    };
+
+    for path in &paths[1..] {
+        while !path.starts_with(&root) {
+            match root.parent() {
+                Some(parent) => root = parent.to_path_buf(),
+                None => break,
+            }
+        }
+    }
+
+    root
+}
+
+fn parent_dir(path: &Path) -> PathBuf {
+    if path.is_dir() {
+        path.to_path_buf()
+    } else {
+        path.parent().unwrap_or(path).to_path_buf()
+    }
+}
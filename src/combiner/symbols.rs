@@ -0,0 +1,141 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+
+#[derive(Deserialize)]
+struct CtagsRecord {
+    name: String,
+    kind: String,
+    line: u64,
+    path: String,
+}
+
+// `available` is false when ctags couldn't be run at all, so callers can
+// tell "no symbols found" apart from "ctags isn't installed".
+pub struct SymbolIndex {
+    pub available: bool,
+    by_file: HashMap<PathBuf, String>,
+}
+
+impl SymbolIndex {
+    pub fn outline_for(&self, path: &Path) -> String {
+        match self.by_file.get(path) {
+            Some(outline) => outline.clone(),
+            None if self.available => "(no symbols)".to_string(),
+            None => "(ctags not available)".to_string(),
+        }
+    }
+
+    // A flat "path: outline" index across every file that produced tags,
+    // for the aggregate `{SYMBOL_INDEX}` field. Paths are rendered relative
+    // to `root`, matching the rest of the output (FILES_INCLUDED, FILE_PATH,
+    // the folder tree) instead of leaking absolute host paths.
+    pub fn aggregate(&self, root: &Path) -> String {
+        if !self.available {
+            return "(ctags not available)".to_string();
+        }
+        if self.by_file.is_empty() {
+            return "(no symbols found)".to_string();
+        }
+        let mut paths: Vec<&PathBuf> = self.by_file.keys().collect();
+        paths.sort();
+        paths
+            .iter()
+            .map(|path| {
+                format!(
+                    "{}:\n{}",
+                    path.strip_prefix(root).unwrap_or(path).display(),
+                    self.by_file[*path]
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+// Chunks the file list across threads since each ctags invocation is
+// independent. Degrades to an empty, unavailable index when ctags isn't
+// installed.
+pub fn extract(files: &[PathBuf]) -> SymbolIndex {
+    let available = ctags_available();
+    if files.is_empty() || !available {
+        return SymbolIndex {
+            available,
+            by_file: HashMap::new(),
+        };
+    }
+
+    let worker_count = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(files.len());
+    let chunk_size = files.len().div_ceil(worker_count.max(1));
+
+    let handles: Vec<_> = files
+        .chunks(chunk_size.max(1))
+        .map(|chunk| {
+            let chunk = chunk.to_vec();
+            thread::spawn(move || run_ctags(&chunk))
+        })
+        .collect();
+
+    let mut by_file: HashMap<PathBuf, Vec<(u64, String, String)>> = HashMap::new();
+    for handle in handles {
+        let records = handle.join().unwrap_or_default();
+        for record in records {
+            by_file
+                .entry(PathBuf::from(record.path))
+                .or_default()
+                .push((record.line, record.name, record.kind));
+        }
+    }
+
+    let by_file = by_file
+        .into_iter()
+        .map(|(path, mut tags)| {
+            tags.sort_by_key(|(line, _, _)| *line);
+            let outline = tags
+                .iter()
+                .map(|(line, name, kind)| format!("{:<6} {} ({})", line, name, kind))
+                .collect::<Vec<_>>()
+                .join("\n");
+            (path, outline)
+        })
+        .collect();
+
+    SymbolIndex {
+        available: true,
+        by_file,
+    }
+}
+
+fn ctags_available() -> bool {
+    Command::new("ctags")
+        .arg("--version")
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+fn run_ctags(files: &[PathBuf]) -> Vec<CtagsRecord> {
+    let Ok(output) = Command::new("ctags")
+        .arg("--output-format=json")
+        .arg("-f")
+        .arg("-")
+        .args(files)
+        .output()
+    else {
+        return Vec::new();
+    };
+
+    if !output.status.success() {
+        return Vec::new();
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<CtagsRecord>(line).ok())
+        .collect()
+}
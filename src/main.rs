@@ -1,15 +1,15 @@
 use clap::{Parser, Subcommand};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
 mod combiner;
+use combiner::ignore::IgnoreMatcher;
 use combiner::FolderProcessOptions;
 
 #[derive(Parser)]
 #[clap(author, version, about = "A tool for combining and analyzing files in a directory", long_about = None)]
 #[clap(after_help = "Example usage:
   fcom combine /path/to/folder -o output.txt -e rs,toml -i target -l -m markdown
+  fcom combine src extra_config.toml -o output.txt --symbols
   fcom tree /path/to/folder -o tree.txt
   fcom list /path/to/folder -o list.txt")]
 pub struct Cli {
@@ -24,8 +24,8 @@ pub enum Commands {
         long_about = "Combine files in a folder, with options to filter by extension, ignore certain files/folders, add line numbers, and choose output format"
     )]
     Combine {
-        #[clap(help = "Path to the folder to process")]
-        folder_path: PathBuf,
+        #[clap(required = true, help = "Paths to files or folders to process")]
+        paths: Vec<PathBuf>,
         #[clap(
             short = 'o',
             long,
@@ -60,11 +60,33 @@ pub enum Commands {
         custom_output_template: Option<PathBuf>,
         #[clap(long, help = "Path to custom file template file")]
         custom_file_template: Option<PathBuf>,
+        #[clap(
+            long,
+            overrides_with = "no_hidden",
+            help = "Include dotfiles and dot-directories in traversal"
+        )]
+        hidden: bool,
+        #[clap(
+            long,
+            overrides_with = "hidden",
+            help = "Exclude dotfiles and dot-directories (default)"
+        )]
+        no_hidden: bool,
+        #[clap(
+            long,
+            help = "Don't load .gitignore or .fcomignore (hardcoded defaults still apply)"
+        )]
+        no_ignore: bool,
+        #[clap(
+            long,
+            help = "Emit a per-file symbol outline (via ctags) alongside the contents"
+        )]
+        symbols: bool,
     },
     #[clap(about = "Generate a folder tree")]
     Tree {
-        #[clap(help = "Path to the folder to process")]
-        folder_path: PathBuf,
+        #[clap(required = true, help = "Paths to files or folders to process")]
+        paths: Vec<PathBuf>,
         #[clap(
             short = 'o',
             long,
@@ -85,11 +107,28 @@ pub enum Commands {
             help = "Folders to ignore (comma-separated)"
         )]
         ignore: Vec<String>,
+        #[clap(
+            long,
+            overrides_with = "no_hidden",
+            help = "Include dotfiles and dot-directories in traversal"
+        )]
+        hidden: bool,
+        #[clap(
+            long,
+            overrides_with = "hidden",
+            help = "Exclude dotfiles and dot-directories (default)"
+        )]
+        no_hidden: bool,
+        #[clap(
+            long,
+            help = "Don't load .gitignore or .fcomignore (hardcoded defaults still apply)"
+        )]
+        no_ignore: bool,
     },
     #[clap(about = "Generate a list of files")]
     List {
-        #[clap(help = "Path to the folder to process")]
-        folder_path: PathBuf,
+        #[clap(required = true, help = "Paths to files or folders to process")]
+        paths: Vec<PathBuf>,
         #[clap(
             short = 'o',
             long,
@@ -110,33 +149,43 @@ pub enum Commands {
             help = "Folders to ignore (comma-separated)"
         )]
         ignore: Vec<String>,
+        #[clap(
+            long,
+            overrides_with = "no_hidden",
+            help = "Include dotfiles and dot-directories in traversal"
+        )]
+        hidden: bool,
+        #[clap(
+            long,
+            overrides_with = "hidden",
+            help = "Exclude dotfiles and dot-directories (default)"
+        )]
+        no_hidden: bool,
+        #[clap(
+            long,
+            help = "Don't load .gitignore or .fcomignore (hardcoded defaults still apply)"
+        )]
+        no_ignore: bool,
     },
 }
 
-fn read_gitignore(folder_path: &Path) -> Vec<String> {
-    let gitignore_path = folder_path.join(".gitignore");
-    let mut ignore_patterns = vec![
+fn build_ignore_matcher(folder_path: &Path, extra: &[String], no_ignore: bool) -> IgnoreMatcher {
+    let mut patterns = default_ignore_patterns();
+    if !no_ignore {
+        patterns.extend(combiner::ignore::read_ignore_file(&folder_path.join(".gitignore")));
+        patterns.extend(combiner::ignore::read_ignore_file(&folder_path.join(".fcomignore")));
+    }
+    patterns.extend(extra.iter().cloned());
+    IgnoreMatcher::compile(&patterns)
+}
+
+fn default_ignore_patterns() -> Vec<String> {
+    vec![
         ".git".to_string(),
         "node_modules".to_string(),
         "__pycache__".to_string(),
         "target".to_string(),
-    ];
-
-    if gitignore_path.exists() {
-        if let Ok(file) = File::open(gitignore_path) {
-            let reader = BufReader::new(file);
-            for pattern in reader.lines().map_while(Result::ok) {
-                let trimmed = pattern.trim();
-                if !trimmed.is_empty() && !trimmed.starts_with('#') {
-                    ignore_patterns.push(trimmed.to_string());
-                }
-            }
-        } else {
-            eprintln!("Failed to open .gitignore file");
-        }
-    }
-
-    ignore_patterns
+    ]
 }
 
 fn main() {
@@ -144,7 +193,7 @@ fn main() {
 
     match &cli.command {
         Commands::Combine {
-            folder_path,
+            paths,
             output,
             extensions,
             ignore,
@@ -152,49 +201,72 @@ fn main() {
             mode,
             custom_output_template,
             custom_file_template,
+            hidden,
+            no_hidden: _,
+            no_ignore,
+            symbols,
         } => {
-            let mut ignore_patterns = read_gitignore(folder_path);
-            if let Some(additional_ignores) = ignore {
-                ignore_patterns.extend(additional_ignores.iter().cloned());
-            }
+            let root = combiner::resolve_root(paths);
+            let ignore_matcher = build_ignore_matcher(
+                &root,
+                ignore.as_deref().unwrap_or_default(),
+                *no_ignore,
+            );
 
             let options = FolderProcessOptions {
-                folder_path,
+                paths,
                 output_file: output,
                 file_extensions: extensions.as_ref().map(|e| e.as_slice()),
-                ignore_patterns: &ignore_patterns,
+                ignore_matcher: &ignore_matcher,
                 add_line_numbers: *add_line_numbers,
                 mode,
                 custom_output_template: custom_output_template.as_ref(),
                 custom_file_template: custom_file_template.as_ref(),
+                include_hidden: *hidden,
+                include_symbols: *symbols,
+                no_ignore: *no_ignore,
             };
 
             combiner::process_folder(options);
         }
         Commands::Tree {
-            folder_path,
+            paths,
             output,
             extensions,
             ignore,
+            hidden,
+            no_hidden: _,
+            no_ignore,
         } => {
+            let root = combiner::resolve_root(paths);
+            let ignore_matcher = build_ignore_matcher(&root, ignore, *no_ignore);
             let tree = combiner::create_folder_tree(
-                folder_path,
+                paths,
                 extensions.as_ref().map(|e| e.as_slice()),
-                ignore,
+                &ignore_matcher,
+                *hidden,
+                *no_ignore,
             );
             std::fs::write(output, tree).expect("Unable to write folder tree to file");
             println!("Folder tree has been generated and saved to '{}'.", output);
         }
         Commands::List {
-            folder_path,
+            paths,
             output,
             extensions,
             ignore,
+            hidden,
+            no_hidden: _,
+            no_ignore,
         } => {
+            let root = combiner::resolve_root(paths);
+            let ignore_matcher = build_ignore_matcher(&root, ignore, *no_ignore);
             let list = combiner::create_file_list(
-                folder_path,
+                paths,
                 extensions.as_ref().map(|e| e.as_slice()),
-                ignore,
+                &ignore_matcher,
+                *hidden,
+                *no_ignore,
             );
             std::fs::write(output, list).expect("Unable to write file list to file");
             println!("File list has been generated and saved to '{}'.", output);